@@ -0,0 +1,247 @@
+//! Sparse cellular automata on an unbounded 2D grid.
+
+use std::collections::{HashMap, HashSet};
+
+pub type Cell = (i32, i32);
+
+/// The eight surrounding cells.
+pub const MOORE: [Cell; 8] = [
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (-1, 0),
+    (1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+];
+
+/// Inclusive bounding box of cells.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Bounds {
+    pub min: Cell,
+    pub max: Cell,
+}
+
+impl Bounds {
+    fn of(cell: Cell) -> Self {
+        Self {
+            min: cell,
+            max: cell,
+        }
+    }
+
+    fn include(&mut self, (x, y): Cell) {
+        self.min = (self.min.0.min(x), self.min.1.min(y));
+        self.max = (self.max.0.max(x), self.max.1.max(y));
+    }
+
+    pub fn width(&self) -> usize {
+        (self.max.0 - self.min.0 + 1) as usize
+    }
+
+    pub fn height(&self) -> usize {
+        (self.max.1 - self.min.1 + 1) as usize
+    }
+
+    pub fn area(&self) -> usize {
+        self.width() * self.height()
+    }
+}
+
+/// Automaton storing only live cells. Each step counts live neighbors by accumulating from every
+/// live cell, so the work is proportional to the population rather than the covered area.
+/// The set for the next generation and the count map are kept between steps to reuse their
+/// allocations.
+#[derive(Debug, Clone)]
+pub struct SparseAutomaton {
+    cells: HashSet<Cell>,
+    next: HashSet<Cell>,
+    counts: HashMap<Cell, u8>,
+    neighborhood: Vec<Cell>,
+    bounds: Option<Bounds>,
+    generation: usize,
+}
+
+impl SparseAutomaton {
+    /// Automaton with the given live cells and the Moore neighborhood.
+    pub fn new<I>(cells: I) -> Self
+    where
+        I: IntoIterator<Item = Cell>,
+    {
+        let cells = cells.into_iter().collect::<HashSet<_>>();
+        let bounds = Self::bounds_of(&cells);
+        Self {
+            cells,
+            next: HashSet::new(),
+            counts: HashMap::new(),
+            neighborhood: MOORE.to_vec(),
+            bounds,
+            generation: 0,
+        }
+    }
+
+    /// Automaton from lines of text where `#` marks a live cell, with the first line at `y = 0`.
+    pub fn parse(s: &str) -> Self {
+        Self::new(s.lines().enumerate().flat_map(|(y, line)| {
+            line.chars()
+                .enumerate()
+                .filter(|(_, c)| *c == '#')
+                .map(move |(x, _)| (x as i32, y as i32))
+        }))
+    }
+
+    pub fn with_neighborhood(self, offsets: &[Cell]) -> Self {
+        Self {
+            neighborhood: offsets.to_vec(),
+            ..self
+        }
+    }
+
+    fn bounds_of(cells: &HashSet<Cell>) -> Option<Bounds> {
+        let mut iter = cells.iter();
+        let mut bounds = Bounds::of(*iter.next()?);
+        for &c in iter {
+            bounds.include(c);
+        }
+        Some(bounds)
+    }
+
+    /// Advances one generation, `rule` decides from whether a cell is alive and its number of
+    /// live neighbors if it's alive in the next generation. Cells without live neighbors are
+    /// never considered, so the rule must keep those dead.
+    pub fn step<R>(&mut self, rule: R)
+    where
+        R: Fn(bool, u8) -> bool,
+    {
+        self.counts.clear();
+        for &(x, y) in &self.cells {
+            self.counts.entry((x, y)).or_insert(0);
+            for &(dx, dy) in &self.neighborhood {
+                *self.counts.entry((x + dx, y + dy)).or_insert(0) += 1;
+            }
+        }
+
+        self.next.clear();
+        let mut bounds = None::<Bounds>;
+        for (&cell, &count) in &self.counts {
+            if rule(self.cells.contains(&cell), count) {
+                self.next.insert(cell);
+                match bounds.as_mut() {
+                    Some(b) => b.include(cell),
+                    None => bounds = Some(Bounds::of(cell)),
+                }
+            }
+        }
+
+        std::mem::swap(&mut self.cells, &mut self.next);
+        self.bounds = bounds;
+        self.generation += 1;
+    }
+
+    pub fn cells(&self) -> &HashSet<Cell> {
+        &self.cells
+    }
+
+    pub fn is_alive(&self, cell: Cell) -> bool {
+        self.cells.contains(&cell)
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// Bounding box of the live cells, `None` when there are none.
+    pub fn bounds(&self) -> Option<Bounds> {
+        self.bounds
+    }
+
+    pub fn generation(&self) -> usize {
+        self.generation
+    }
+
+    /// Live cells within the bounding box as `#` and `.` lines.
+    pub fn render(&self) -> String {
+        let Some(Bounds { min, max }) = self.bounds else {
+            return String::new();
+        };
+        (min.1..=max.1)
+            .map(|y| {
+                (min.0..=max.0)
+                    .map(|x| if self.is_alive((x, y)) { '#' } else { '.' })
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+/// Conway's Game of Life, born with three neighbors and surviving with two or three.
+pub fn life(alive: bool, neighbors: u8) -> bool {
+    matches!((alive, neighbors), (true, 2) | (_, 3))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_blinker() {
+        let mut ca = SparseAutomaton::parse("###");
+        ca.step(life);
+        assert_eq!(ca.render(), "#\n#\n#");
+        assert_eq!(
+            ca.bounds(),
+            Some(Bounds {
+                min: (1, -1),
+                max: (1, 1)
+            })
+        );
+        ca.step(life);
+        assert_eq!(ca.render(), "###");
+        assert_eq!(ca.generation(), 2);
+    }
+
+    #[test]
+    fn test_glider() {
+        let glider = ".#.\n..#\n###";
+        let mut ca = SparseAutomaton::parse(glider);
+        for _ in 0..4 {
+            ca.step(life);
+        }
+        // Same shape moved one step down and right
+        assert_eq!(ca.render(), glider);
+        assert_eq!(ca.bounds().unwrap().min, (1, 1));
+        assert_eq!(ca.len(), 5);
+    }
+
+    #[test]
+    fn test_still_life_and_death() {
+        let mut block = SparseAutomaton::parse("##\n##");
+        block.step(life);
+        assert_eq!(block.render(), "##\n##");
+
+        let mut single = SparseAutomaton::parse("#");
+        single.step(life);
+        assert!(single.is_empty());
+        assert_eq!(single.bounds(), None);
+        assert_eq!(single.render(), "");
+    }
+
+    #[test]
+    fn test_neighborhood_and_empty_ground() {
+        // Von Neumann neighborhood, cells with exactly one neighbor come alive
+        let cross = [(0, -1), (-1, 0), (1, 0), (0, 1)];
+        let mut ca = SparseAutomaton::new([(0, 0)]).with_neighborhood(&cross);
+        ca.step(|alive, n| !alive && n == 1);
+        assert_eq!(ca.render(), ".#.\n#.#\n.#.");
+        let bounds = ca.bounds().unwrap();
+        assert_eq!((bounds.width(), bounds.height()), (3, 3));
+        // Empty ground within the bounding box as counted by day23
+        assert_eq!(bounds.area() - ca.len(), 5);
+    }
+}
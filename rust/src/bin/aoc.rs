@@ -0,0 +1,330 @@
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+
+use utils::generate::{self, Generator, Rng, Sample, GENERATORS};
+use utils::meta;
+
+const USAGE: &str = "Usage:
+  aoc gen (<day> | --all) [--out <dir>] [--seed <n>] [--size <n>]
+  aoc verify --synthetic [--dir <dir>]
+  aoc growth-bench (<day> | --all) [--base-size <n>] [--steps <n>]";
+
+const DEFAULT_SYNTHETIC_DIR: &str = "target/synthetic";
+const DEFAULT_SEED: u64 = 2022;
+const MANIFEST: &str = "manifest";
+const GROWTH_DIR: &str = "target/growth";
+const GROWTH_STEPS: u32 = 5;
+// Allowed excess over the annotated exponent before a day is flagged, measurements are noisy
+const GROWTH_TOLERANCE: f64 = 0.35;
+
+#[derive(Debug, PartialEq)]
+struct ManifestEntry {
+    day: u8,
+    part1: String,
+    part2: String,
+}
+
+#[derive(Debug, Default, PartialEq)]
+struct Answers {
+    part1: Option<String>,
+    part2: Option<String>,
+    took: Option<Duration>,
+}
+
+fn opt<'a>(args: &[&'a str], name: &str) -> Option<&'a str> {
+    args.iter()
+        .position(|a| *a == name)
+        .and_then(|i| args.get(i + 1).copied())
+}
+
+fn flag(args: &[&str], name: &str) -> bool {
+    args.contains(&name)
+}
+
+fn parse_day(s: &str) -> Result<u8> {
+    let day = s
+        .parse::<u8>()
+        .with_context(|| format!("Invalid day {:?}", s))?;
+    anyhow::ensure!((1..=25).contains(&day), "Day out of range {}", day);
+    Ok(day)
+}
+
+fn day_name(day: u8) -> String {
+    format!("day{:02}", day)
+}
+
+/// Day solutions are separate binaries, they are expected to be built next to this one.
+fn day_binary(day: u8) -> Result<PathBuf> {
+    let exe = env::current_exe()?;
+    let dir = exe.parent().context("No binary directory")?;
+    let path = dir.join(format!("{}{}", day_name(day), env::consts::EXE_SUFFIX));
+    anyhow::ensure!(
+        path.exists(),
+        "{} not found, build all binaries first with: cargo build --release",
+        path.display()
+    );
+    Ok(path)
+}
+
+fn run_day(day: u8, input: &Path) -> Result<Answers> {
+    let output = Command::new(day_binary(day)?).arg(input).output()?;
+    anyhow::ensure!(
+        output.status.success(),
+        "{} failed: {}",
+        day_name(day),
+        String::from_utf8_lossy(&output.stderr).trim()
+    );
+    Ok(parse_answers(&String::from_utf8_lossy(&output.stdout)))
+}
+
+fn parse_answers(stdout: &str) -> Answers {
+    let mut answers = Answers::default();
+    for line in stdout.lines() {
+        if let Some(v) = line.strip_prefix("Part1: ") {
+            answers.part1 = Some(v.trim().to_owned());
+        } else if let Some(v) = line.strip_prefix("Part2: ") {
+            answers.part2 = Some(v.trim().to_owned());
+        } else if let Some(v) = line.strip_prefix("It took: ") {
+            answers.took = v
+                .split("ms")
+                .next()
+                .and_then(|ms| ms.parse::<f64>().ok())
+                .map(|ms| Duration::from_secs_f64(ms / 1000.0));
+        }
+    }
+    answers
+}
+
+fn day_generators(args: &[&str]) -> Result<Vec<&'static Generator>> {
+    if flag(args, "--all") {
+        Ok(GENERATORS.iter().collect())
+    } else {
+        let day = parse_day(args.first().context(USAGE)?)?;
+        let g =
+            generate::generator(day).with_context(|| format!("No generator for day {}", day))?;
+        Ok(vec![g])
+    }
+}
+
+fn format_manifest(seed: u64, entries: &[ManifestEntry]) -> String {
+    let mut s = format!("# seed {}\n", seed);
+    for ManifestEntry { day, part1, part2 } in entries {
+        s.push_str(&format!("{}\t{}\t{}\n", day_name(*day), part1, part2));
+    }
+    s
+}
+
+fn parse_manifest(s: &str) -> Result<Vec<ManifestEntry>> {
+    s.lines()
+        .filter(|l| !l.is_empty() && !l.starts_with('#'))
+        .map(|l| match l.split('\t').collect::<Vec<_>>()[..] {
+            [day, part1, part2] => Ok(ManifestEntry {
+                day: parse_day(day.trim_start_matches("day"))?,
+                part1: part1.to_owned(),
+                part2: part2.to_owned(),
+            }),
+            _ => anyhow::bail!("Invalid manifest line {:?}", l),
+        })
+        .collect()
+}
+
+fn gen(args: &[&str]) -> Result<()> {
+    let out = PathBuf::from(opt(args, "--out").unwrap_or(DEFAULT_SYNTHETIC_DIR));
+    let seed = opt(args, "--seed").map_or(Ok(DEFAULT_SEED), |s| s.parse())?;
+    let size = opt(args, "--size")
+        .map(|s| s.parse::<usize>())
+        .transpose()?;
+
+    fs::create_dir_all(&out)?;
+    let mut entries = vec![];
+    for g in day_generators(args)? {
+        // Each day gets its own stream so adding a generator doesn't change the others
+        let mut rng = Rng::new(seed ^ g.day as u64);
+        let Sample {
+            input,
+            part1,
+            part2,
+        } = (g.generate)(&mut rng, size.unwrap_or(g.default_size));
+
+        fs::write(out.join(day_name(g.day)), input)?;
+        println!("Generated {}", day_name(g.day));
+        entries.push(ManifestEntry {
+            day: g.day,
+            part1,
+            part2,
+        });
+    }
+
+    fs::write(out.join(MANIFEST), format_manifest(seed, &entries))?;
+    println!("Wrote {}", out.join(MANIFEST).display());
+    Ok(())
+}
+
+fn verify(args: &[&str]) -> Result<()> {
+    anyhow::ensure!(flag(args, "--synthetic"), USAGE);
+    let dir = PathBuf::from(opt(args, "--dir").unwrap_or(DEFAULT_SYNTHETIC_DIR));
+    let manifest = fs::read_to_string(dir.join(MANIFEST))
+        .with_context(|| format!("No manifest in {}, run aoc gen --all", dir.display()))?;
+
+    let mut failures = 0;
+    for entry in parse_manifest(&manifest)? {
+        let answers = run_day(entry.day, &dir.join(day_name(entry.day)))?;
+        let mut ok = true;
+        for (part, expected, actual) in [
+            (1, &entry.part1, &answers.part1),
+            (2, &entry.part2, &answers.part2),
+        ] {
+            if actual.as_ref() != Some(expected) {
+                ok = false;
+                println!(
+                    "{} part{}: expected {} got {}",
+                    day_name(entry.day),
+                    part,
+                    expected,
+                    actual.as_deref().unwrap_or("nothing")
+                );
+            }
+        }
+        if ok {
+            println!("{} ok", day_name(entry.day));
+        } else {
+            failures += 1;
+        }
+    }
+
+    anyhow::ensure!(failures == 0, "{} day(s) failed verification", failures);
+    Ok(())
+}
+
+/// Least squares fit of the exponent `k` in `t = c * n^k` over (size, seconds) measurements.
+fn fit_exponent(points: &[(f64, f64)]) -> f64 {
+    let logs = points
+        .iter()
+        .map(|(n, t)| (n.ln(), t.ln()))
+        .collect::<Vec<_>>();
+    let len = logs.len() as f64;
+    let mean_x = logs.iter().map(|(x, _)| x).sum::<f64>() / len;
+    let mean_y = logs.iter().map(|(_, y)| y).sum::<f64>() / len;
+    let cov = logs
+        .iter()
+        .map(|(x, y)| (x - mean_x) * (y - mean_y))
+        .sum::<f64>();
+    let var = logs.iter().map(|(x, _)| (x - mean_x).powi(2)).sum::<f64>();
+    cov / var
+}
+
+fn growth_bench(args: &[&str]) -> Result<()> {
+    let steps = opt(args, "--steps").map_or(Ok(GROWTH_STEPS), |s| s.parse())?;
+    let base_size = opt(args, "--base-size")
+        .map(|s| s.parse::<usize>())
+        .transpose()?;
+    anyhow::ensure!(steps >= 2, "At least two steps are needed to fit a curve");
+
+    let dir = PathBuf::from(GROWTH_DIR);
+    fs::create_dir_all(&dir)?;
+
+    let mut flagged = vec![];
+    for g in day_generators(args)? {
+        let meta = meta::day(g.day).context("No metadata")?;
+        let mut points = vec![];
+
+        for step in 0..steps {
+            let size = base_size.unwrap_or(g.default_size * 8) << step;
+            let sample = (g.generate)(&mut Rng::new(DEFAULT_SEED), size);
+            let path = dir.join(format!("{}-{}", day_name(g.day), size));
+            fs::write(&path, &sample.input)?;
+
+            // Least of a few runs to reduce scheduler noise
+            let mut least = None::<Duration>;
+            for _ in 0..3 {
+                let took = run_day(g.day, &path)?.took.context("No timing in output")?;
+                least = Some(least.map_or(took, |l| l.min(took)));
+            }
+            let took = least.unwrap();
+            println!(
+                "{} n={:<10} {:.3}ms",
+                day_name(g.day),
+                sample.input.len(),
+                took.as_secs_f64() * 1000.0
+            );
+            points.push((sample.input.len() as f64, took.as_secs_f64()));
+        }
+
+        let measured = fit_exponent(&points);
+        let expected = meta.complexity.exponent();
+        let ok = measured <= expected + GROWTH_TOLERANCE;
+        println!(
+            "{} measured n^{:.2}, annotated {} {}",
+            day_name(g.day),
+            measured,
+            meta.complexity,
+            if ok { "ok" } else { "EXCEEDED" }
+        );
+        if !ok {
+            flagged.push(day_name(g.day));
+        }
+    }
+
+    anyhow::ensure!(
+        flagged.is_empty(),
+        "Growth exceeds annotated complexity for {}",
+        flagged.join(", ")
+    );
+    Ok(())
+}
+
+fn main() -> Result<()> {
+    let args = env::args().skip(1).collect::<Vec<_>>();
+    let args = args.iter().map(|s| s.as_str()).collect::<Vec<_>>();
+
+    match args[..] {
+        ["gen", ref rest @ ..] => gen(rest),
+        ["verify", ref rest @ ..] => verify(rest),
+        ["growth-bench", ref rest @ ..] => growth_bench(rest),
+        _ => anyhow::bail!(USAGE),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_manifest_roundtrip() -> Result<()> {
+        let entries = vec![
+            ManifestEntry {
+                day: 1,
+                part1: "24000".to_owned(),
+                part2: "45000".to_owned(),
+            },
+            ManifestEntry {
+                day: 2,
+                part1: "15".to_owned(),
+                part2: "12".to_owned(),
+            },
+        ];
+        assert_eq!(parse_manifest(&format_manifest(1, &entries))?, entries);
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_answers() {
+        let answers = parse_answers("Part1: 15\nPart2: 12\nIt took: 0.5ms\n");
+        assert_eq!(answers.part1.as_deref(), Some("15"));
+        assert_eq!(answers.part2.as_deref(), Some("12"));
+        assert_eq!(answers.took, Some(Duration::from_micros(500)));
+    }
+
+    #[test]
+    fn test_fit_exponent() {
+        let linear = [(10.0, 1.0), (20.0, 2.0), (40.0, 4.0)];
+        assert!((fit_exponent(&linear) - 1.0).abs() < 1e-9);
+        let quadratic = [(10.0, 1.0), (20.0, 4.0), (40.0, 16.0)];
+        assert!((fit_exponent(&quadratic) - 2.0).abs() < 1e-9);
+    }
+}
@@ -52,6 +52,8 @@ fn input() -> Result<Input> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use utils::generate::day01::{calorie_list, Params};
+    use utils::generate::Rng;
 
     const INPUT: &str = "
         1000
@@ -91,4 +93,27 @@ mod tests {
         assert_eq!(solve(&as_input(INPUT)?).1, 45000);
         Ok(())
     }
+
+    #[test]
+    fn test_generated() -> Result<()> {
+        let params = [
+            Params::default(),
+            Params {
+                groups: 3,
+                group_size: (1, 1),
+                calories: (1, 10),
+            },
+            Params {
+                groups: 2000,
+                group_size: (5, 30),
+                calories: (1, 99999),
+            },
+        ];
+        for (seed, params) in params.iter().enumerate() {
+            let sample = calorie_list(&mut Rng::new(seed as u64), params);
+            let input = read_input(BufReader::new(sample.input.as_bytes()))?;
+            assert_eq!(solve(&input), (sample.part1, sample.part2));
+        }
+        Ok(())
+    }
 }
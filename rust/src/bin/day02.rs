@@ -145,6 +145,8 @@ fn input() -> Result<Input> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use utils::generate::day02::strategy_guide;
+    use utils::generate::Rng;
 
     const INPUT: &str = "
         A Y
@@ -173,4 +175,15 @@ mod tests {
         assert_eq!(part2(&as_input(INPUT)?), 12);
         Ok(())
     }
+
+    #[test]
+    fn test_generated() -> Result<()> {
+        for seed in 0..10 {
+            let sample = strategy_guide(&mut Rng::new(seed), 1000);
+            let input = read_input(BufReader::new(sample.input.as_bytes()))?;
+            assert_eq!(part1(&input), sample.part1);
+            assert_eq!(part2(&input), sample.part2);
+        }
+        Ok(())
+    }
 }
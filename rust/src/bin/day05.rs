@@ -65,7 +65,7 @@ fn part2(input: &Input) -> String {
 fn top_letters(stacks: Vec<String>) -> String {
     stacks
         .iter()
-        .filter_map(|s| s.chars().rev().next())
+        .filter_map(|s| s.chars().next_back())
         .collect()
 }
 
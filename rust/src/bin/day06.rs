@@ -56,6 +56,8 @@ fn input() -> Result<Input> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use utils::generate::day06::datastream;
+    use utils::generate::Rng;
 
     const INPUT: &str = "mjqjpqmgbljsphdztnvjfqwrcgsmlb";
 
@@ -80,4 +82,15 @@ mod tests {
         assert_eq!(part2(&as_input(INPUT)?), 19);
         Ok(())
     }
+
+    #[test]
+    fn test_generated() -> Result<()> {
+        for seed in 0..10 {
+            let sample = datastream(&mut Rng::new(seed), 200);
+            let input = as_input(&sample.input)?;
+            assert_eq!(part1(&input), sample.part1);
+            assert_eq!(part2(&input), sample.part2);
+        }
+        Ok(())
+    }
 }
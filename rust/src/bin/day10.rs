@@ -95,7 +95,7 @@ fn part1(input: &Input) -> i32 {
 
     captured
         .into_iter()
-        .zip(capture_points.into_iter())
+        .zip(capture_points)
         .map(|(a, b)| a * b as i32)
         .sum()
 }
@@ -49,7 +49,7 @@ where
 
                 let new_worry_level = manage_worry_level_fn(new_worry_level);
 
-                let is_devisable = new_worry_level % monkey.test_div == 0;
+                let is_devisable = new_worry_level.is_multiple_of(monkey.test_div);
 
                 let target = if is_devisable {
                     monkey.true_to
@@ -166,7 +166,7 @@ impl FromStr for Value {
                 _ => {
                     let s = &s[idx..]
                         .chars()
-                        .take_while(|&c| ('0'..='9').contains(&c))
+                        .take_while(|&c| c.is_ascii_digit())
                         .collect::<String>();
 
                     let v = s.parse::<u8>()?;
@@ -0,0 +1,442 @@
+//! Folding of flat cube nets, e.g. the map of day22, into face adjacencies.
+
+use std::collections::VecDeque;
+
+use anyhow::Result;
+
+/// Direction on the flat net, numbered like the facings of day22.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Dir {
+    Right = 0,
+    Down = 1,
+    Left = 2,
+    Up = 3,
+}
+
+impl Dir {
+    pub const ALL: [Dir; 4] = [Dir::Right, Dir::Down, Dir::Left, Dir::Up];
+
+    pub fn opposite(self) -> Dir {
+        Self::ALL[(self as usize + 2) % 4]
+    }
+
+    pub fn delta(self) -> (i64, i64) {
+        match self {
+            Dir::Right => (1, 0),
+            Dir::Down => (0, 1),
+            Dir::Left => (-1, 0),
+            Dir::Up => (0, -1),
+        }
+    }
+}
+
+type Vec3 = [i32; 3];
+
+fn neg(v: Vec3) -> Vec3 {
+    [-v[0], -v[1], -v[2]]
+}
+
+/// Placement of a face on the folded cube, the outward normal and where the face's right and
+/// down directions on the net point in space.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Frame {
+    normal: Vec3,
+    right: Vec3,
+    down: Vec3,
+}
+
+impl Frame {
+    fn dir(&self, dir: Dir) -> Vec3 {
+        match dir {
+            Dir::Right => self.right,
+            Dir::Down => self.down,
+            Dir::Left => neg(self.right),
+            Dir::Up => neg(self.down),
+        }
+    }
+
+    /// Frame of the face reached by folding over the edge in direction `dir`.
+    fn fold(&self, dir: Dir) -> Frame {
+        let Frame {
+            normal,
+            right,
+            down,
+        } = *self;
+        match dir {
+            Dir::Right => Frame {
+                normal: right,
+                right: neg(normal),
+                down,
+            },
+            Dir::Left => Frame {
+                normal: neg(right),
+                right: normal,
+                down,
+            },
+            Dir::Down => Frame {
+                normal: down,
+                right,
+                down: neg(normal),
+            },
+            Dir::Up => Frame {
+                normal: neg(down),
+                right,
+                down: normal,
+            },
+        }
+    }
+}
+
+/// The edge of a face, given as the side of the face it's on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Edge {
+    pub face: usize,
+    pub side: Dir,
+}
+
+#[derive(Debug, Clone)]
+pub struct CubeNet {
+    size: usize,
+    /// Position of every face on the net in face units, in reading order.
+    origins: Vec<(usize, usize)>,
+    frames: Vec<Frame>,
+}
+
+impl CubeNet {
+    /// Net from a layout in face units where `#` marks a face, e.g. `["..#", "###", "..##"]`.
+    pub fn from_layout(layout: &[&str], size: usize) -> Result<Self> {
+        let origins = layout
+            .iter()
+            .enumerate()
+            .flat_map(|(y, row)| {
+                row.chars()
+                    .enumerate()
+                    .filter(|(_, c)| *c == '#')
+                    .map(move |(x, _)| (x, y))
+            })
+            .collect();
+        Self::new(origins, size)
+    }
+
+    /// Net from a full map where every non space character is a tile, the face size is derived
+    /// from the number of tiles.
+    pub fn from_map(map: &[&str]) -> Result<Self> {
+        let tiles = map
+            .iter()
+            .map(|row| row.chars().filter(|c| *c != ' ').count())
+            .sum::<usize>();
+        let size = ((tiles / 6) as f64).sqrt() as usize;
+        anyhow::ensure!(
+            size > 0 && size * size * 6 == tiles,
+            "{} tiles can't form a cube",
+            tiles
+        );
+
+        let mut origins = vec![];
+        for (y, row) in map.iter().enumerate().step_by(size) {
+            for (x, c) in row.chars().enumerate().step_by(size) {
+                if c != ' ' {
+                    origins.push((x / size, y / size));
+                }
+            }
+        }
+        Self::new(origins, size)
+    }
+
+    fn new(origins: Vec<(usize, usize)>, size: usize) -> Result<Self> {
+        anyhow::ensure!(
+            origins.len() == 6,
+            "A cube net has 6 faces, got {}",
+            origins.len()
+        );
+
+        let mut frames = vec![None; 6];
+        frames[0] = Some(Frame {
+            normal: [0, 0, 1],
+            right: [1, 0, 0],
+            down: [0, 1, 0],
+        });
+        let mut queue = VecDeque::from([0]);
+        while let Some(face) = queue.pop_front() {
+            let frame = frames[face].unwrap();
+            let (x, y) = origins[face];
+            for dir in Dir::ALL {
+                let (dx, dy) = dir.delta();
+                let next = (x as i64 + dx, y as i64 + dy);
+                let adjacent = origins
+                    .iter()
+                    .position(|&(ox, oy)| (ox as i64, oy as i64) == next);
+                if let Some(adjacent) = adjacent {
+                    if frames[adjacent].is_none() {
+                        frames[adjacent] = Some(frame.fold(dir));
+                        queue.push_back(adjacent);
+                    }
+                }
+            }
+        }
+
+        let frames = frames
+            .into_iter()
+            .collect::<Option<Vec<_>>>()
+            .ok_or_else(|| anyhow::anyhow!("Faces of the net aren't connected"))?;
+        for (i, a) in frames.iter().enumerate() {
+            anyhow::ensure!(
+                frames[i + 1..].iter().all(|b| a.normal != b.normal),
+                "Faces overlap when folded, not a cube net"
+            );
+        }
+
+        Ok(Self {
+            size,
+            origins,
+            frames,
+        })
+    }
+
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Top left tile of `face` on the net.
+    pub fn origin(&self, face: usize) -> (usize, usize) {
+        let (x, y) = self.origins[face];
+        (x * self.size, y * self.size)
+    }
+
+    /// Face containing the tile at `x, y` of the net.
+    pub fn face_at(&self, x: usize, y: usize) -> Option<usize> {
+        self.origins
+            .iter()
+            .position(|&o| o == (x / self.size, y / self.size))
+    }
+
+    /// The face on the other side of the edge at `side` of `face` and the side of that face the
+    /// edge is on.
+    pub fn neighbor(&self, face: usize, side: Dir) -> Edge {
+        let towards = self.frames[face].dir(side);
+        let other = self
+            .frames
+            .iter()
+            .position(|f| f.normal == towards)
+            .unwrap();
+        let back = self.frames[face].normal;
+        let other_side = Dir::ALL
+            .into_iter()
+            .find(|&d| self.frames[other].dir(d) == back)
+            .unwrap();
+        Edge {
+            face: other,
+            side: other_side,
+        }
+    }
+
+    /// Steps off `face` from the local tile `x, y` on its `dir` edge, returning the face, local
+    /// tile and direction of travel on the other side.
+    pub fn wrap(
+        &self,
+        face: usize,
+        (x, y): (usize, usize),
+        dir: Dir,
+    ) -> (usize, (usize, usize), Dir) {
+        let last = self.size - 1;
+        // Offsets along edges run clockwise around each face. Folded faces are consistently
+        // oriented so a shared edge runs in opposite directions on the two faces.
+        let offset = match dir {
+            Dir::Right => y,
+            Dir::Down => last - x,
+            Dir::Left => last - y,
+            Dir::Up => x,
+        };
+        let Edge { face: other, side } = self.neighbor(face, dir);
+        let o = last - offset;
+        let pos = match side {
+            Dir::Right => (last, o),
+            Dir::Down => (last - o, last),
+            Dir::Left => (0, last - o),
+            Dir::Up => (o, 0),
+        };
+        (other, pos, side.opposite())
+    }
+
+    /// Moves one tile from `x, y` on the net in `dir`, wrapping around the cube where needed.
+    pub fn step(&self, (x, y): (usize, usize), dir: Dir) -> ((usize, usize), Dir) {
+        let face = self.face_at(x, y).expect("Position outside the net");
+        let (ox, oy) = self.origin(face);
+        let (lx, ly) = (x - ox, y - oy);
+        let (dx, dy) = dir.delta();
+        let (nx, ny) = (lx as i64 + dx, ly as i64 + dy);
+        let size = self.size as i64;
+
+        if (0..size).contains(&nx) && (0..size).contains(&ny) {
+            return ((ox + nx as usize, oy + ny as usize), dir);
+        }
+
+        let (face, (lx, ly), dir) = self.wrap(face, (lx, ly), dir);
+        let (ox, oy) = self.origin(face);
+        ((ox + lx, oy + ly), dir)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NETS: [&[&str]; 11] = [
+        &["#...", "####", "#..."],
+        &["#...", "####", ".#.."],
+        &["#...", "####", "..#."],
+        &["#...", "####", "...#"],
+        &[".#..", "####", ".#.."],
+        &[".#..", "####", "..#."],
+        &["##..", ".###", ".#.."],
+        &["##..", ".###", "..#."],
+        &["##..", ".###", "...#"],
+        &["##..", ".##.", "..##"],
+        &["###..", "..###"],
+    ];
+
+    fn rotate(layout: &[String]) -> Vec<String> {
+        let h = layout.len();
+        let w = layout.iter().map(|r| r.len()).max().unwrap();
+        (0..w)
+            .map(|x| {
+                (0..h)
+                    .rev()
+                    .map(|y| layout[y].chars().nth(x).unwrap_or('.'))
+                    .collect()
+            })
+            .collect()
+    }
+
+    fn mirror(layout: &[String]) -> Vec<String> {
+        layout.iter().map(|r| r.chars().rev().collect()).collect()
+    }
+
+    // Every net in all of its rotations and reflections
+    fn all_nets() -> Vec<Vec<String>> {
+        let mut nets = vec![];
+        for net in NETS {
+            let mut layout = net.iter().map(|r| r.to_string()).collect::<Vec<_>>();
+            for _ in 0..4 {
+                nets.push(layout.clone());
+                nets.push(mirror(&layout));
+                layout = rotate(&layout);
+            }
+        }
+        nets
+    }
+
+    fn net(layout: &[String], size: usize) -> Result<CubeNet> {
+        CubeNet::from_layout(&layout.iter().map(|r| r.as_str()).collect::<Vec<_>>(), size)
+    }
+
+    #[test]
+    fn test_adjacency() -> Result<()> {
+        for layout in all_nets() {
+            let cube = net(&layout, 1)?;
+            for face in 0..6 {
+                let neighbors = Dir::ALL.map(|d| cube.neighbor(face, d));
+                for (i, n) in neighbors.iter().enumerate() {
+                    assert_ne!(n.face, face);
+                    assert!(neighbors[i + 1..].iter().all(|m| m.face != n.face));
+                    // The edge is shared both ways
+                    assert_eq!(
+                        cube.neighbor(n.face, n.side),
+                        Edge {
+                            face,
+                            side: Dir::ALL[i]
+                        }
+                    );
+                }
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_flat_neighbors() -> Result<()> {
+        for layout in all_nets() {
+            let cube = net(&layout, 3)?;
+            for face in 0..6 {
+                let (ox, oy) = cube.origins[face];
+                for dir in Dir::ALL {
+                    let (dx, dy) = dir.delta();
+                    let next = (ox as i64 + dx, oy as i64 + dy);
+                    let flat = cube
+                        .origins
+                        .iter()
+                        .position(|&(x, y)| (x as i64, y as i64) == next);
+                    if let Some(flat) = flat {
+                        let edge = cube.neighbor(face, dir);
+                        assert_eq!(
+                            edge,
+                            Edge {
+                                face: flat,
+                                side: dir.opposite()
+                            }
+                        );
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_walk_around() -> Result<()> {
+        // Walking straight on a cube returns to the start after four faces
+        for layout in all_nets() {
+            let size = 4;
+            let cube = net(&layout, size)?;
+            for face in 0..6 {
+                let (ox, oy) = cube.origin(face);
+                for (lx, ly) in [(0, 0), (1, 2), (3, 1)] {
+                    for dir in Dir::ALL {
+                        let start = (ox + lx, oy + ly);
+                        let (mut pos, mut d) = (start, dir);
+                        for step in 1..=4 * size {
+                            (pos, d) = cube.step(pos, d);
+                            if step < 4 * size {
+                                assert!(pos != start || d != dir);
+                            }
+                        }
+                        assert_eq!((pos, d), (start, dir));
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_day22_example() -> Result<()> {
+        let map = [
+            "        ...#",
+            "        .#..",
+            "        #...",
+            "        ....",
+            "...#.......#",
+            "........#...",
+            "..#....#....",
+            "..........#.",
+            "        ...#....",
+            "        .....#..",
+            "        .#......",
+            "        ......#.",
+        ];
+        let cube = CubeNet::from_map(&map)?;
+        assert_eq!(cube.size(), 4);
+        assert_eq!(cube.step((11, 5), Dir::Right), ((14, 8), Dir::Down));
+        assert_eq!(cube.step((10, 11), Dir::Down), ((1, 7), Dir::Up));
+        assert_eq!(cube.step((6, 4), Dir::Up), ((8, 2), Dir::Right));
+        Ok(())
+    }
+
+    #[test]
+    fn test_invalid_nets() {
+        assert!(CubeNet::from_layout(&["###", "###"], 1).is_err());
+        assert!(CubeNet::from_layout(&["####", "#..#"], 1).is_err());
+        assert!(CubeNet::from_layout(&["###", "...", "###"], 1).is_err());
+        assert!(CubeNet::from_layout(&["##"], 1).is_err());
+    }
+}
@@ -0,0 +1,61 @@
+use std::fmt::Write;
+
+use super::{Rng, Sample};
+
+#[derive(Debug, Clone)]
+pub struct Params {
+    pub groups: usize,
+    /// Inclusive range of the number of items carried per elf, at least one.
+    pub group_size: (usize, usize),
+    /// Inclusive range of the calories of a single item.
+    pub calories: (u32, u32),
+}
+
+impl Default for Params {
+    fn default() -> Self {
+        Self {
+            groups: 250,
+            group_size: (1, 15),
+            calories: (1000, 60000),
+        }
+    }
+}
+
+/// Generates a calorie list with elf groups according to `params`. The top three totals are
+/// tracked while generating so that both answers are known without sorting afterwards.
+pub fn calorie_list(rng: &mut Rng, params: &Params) -> Sample<u32> {
+    let (min_size, max_size) = params.group_size;
+    let (min_cals, max_cals) = params.calories;
+    assert!(min_size > 0, "Elves must carry at least one item");
+
+    let mut input = String::new();
+    let mut top = [0; 3];
+
+    for g in 0..params.groups {
+        if g > 0 {
+            input.push_str("\n\n");
+        }
+
+        let size = rng.between(min_size as u64, max_size as u64) as usize;
+        let mut total = 0;
+        for i in 0..size {
+            if i > 0 {
+                input.push('\n');
+            }
+            let cals = rng.between(min_cals as u64, max_cals as u64) as u32;
+            total += cals;
+            let _ = write!(input, "{}", cals);
+        }
+
+        if let Some(idx) = top.iter().position(|&t| total > t) {
+            top[idx..].rotate_right(1);
+            top[idx] = total;
+        }
+    }
+
+    Sample {
+        input,
+        part1: top[0],
+        part2: top.iter().sum(),
+    }
+}
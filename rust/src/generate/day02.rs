@@ -0,0 +1,41 @@
+use std::fmt::Write;
+
+use super::{Rng, Sample};
+
+/// Generates a strategy guide of `rounds` random lines. The scores for both interpretations of
+/// the second column are computed directly from the shape indices rather than by simulating.
+pub fn strategy_guide(rng: &mut Rng, rounds: usize) -> Sample<u32> {
+    let mut input = String::with_capacity(rounds * 4);
+    let mut part1 = 0;
+    let mut part2 = 0;
+
+    for i in 0..rounds {
+        // 0 = Rock, 1 = Paper, 2 = Scissors
+        let opp = rng.below(3) as u32;
+        let col = rng.below(3) as u32;
+
+        // Part1: column is the shape played, (you - opp) mod 3 gives 0 draw, 1 win, 2 loss
+        let you = col;
+        part1 += you + 1 + [3, 6, 0][((you + 3 - opp) % 3) as usize];
+
+        // Part2: column is the outcome, X loose, Y draw, Z win
+        let you = (opp + [2, 0, 1][col as usize]) % 3;
+        part2 += you + 1 + col * 3;
+
+        if i > 0 {
+            input.push('\n');
+        }
+        let _ = write!(
+            input,
+            "{} {}",
+            (b'A' + opp as u8) as char,
+            (b'X' + col as u8) as char
+        );
+    }
+
+    Sample {
+        input,
+        part1,
+        part2,
+    }
+}
@@ -0,0 +1,33 @@
+use super::{Rng, Sample};
+
+fn push_random(rng: &mut Rng, s: &mut String, alphabet: &[u8], len: usize) {
+    for _ in 0..len {
+        s.push(*rng.pick(alphabet) as char);
+    }
+}
+
+/// Generates a datastream of roughly `size` characters with markers at known positions.
+///
+/// Until the first marker only `a`-`c` are used so no four characters can be distinct, a doubled
+/// `a` followed by `def` then completes the first start-of-packet marker. Until the
+/// start-of-message marker only `a`-`m` are used, a doubled `m` followed by the thirteen
+/// remaining letters then completes it.
+pub fn datastream(rng: &mut Rng, size: usize) -> Sample<usize> {
+    let mut input = String::with_capacity(size + 32);
+
+    push_random(rng, &mut input, b"abc", size / 2);
+    input.push_str("aadef");
+    let part1 = input.len();
+
+    push_random(rng, &mut input, b"abcdefghijklm", size / 2);
+    input.push_str("mmnopqrstuvwxyz");
+    let part2 = input.len();
+
+    push_random(rng, &mut input, b"abcdefghijklmnopqrstuvwxyz", size / 4);
+
+    Sample {
+        input,
+        part1,
+        part2,
+    }
+}
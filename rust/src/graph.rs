@@ -0,0 +1,5 @@
+//! Graph algorithms shared between days.
+
+pub mod dense;
+pub mod timed;
+pub mod topo;
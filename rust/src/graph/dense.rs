@@ -0,0 +1,250 @@
+//! Dense all-pairs distances for small weighted graphs.
+
+/// All-pairs distance matrix over nodes `0..len`, unreachable pairs have no distance.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DistanceMatrix {
+    len: usize,
+    dist: Vec<u32>,
+}
+
+const INF: u32 = u32::MAX;
+
+impl DistanceMatrix {
+    /// Matrix where every node only reaches itself.
+    pub fn new(len: usize) -> Self {
+        let mut dist = vec![INF; len * len];
+        for i in 0..len {
+            dist[i * len + i] = 0;
+        }
+        Self { len, dist }
+    }
+
+    /// Matrix from directed `(from, to, weight)` edges, the lightest edge wins for duplicates.
+    pub fn from_edges<I>(len: usize, edges: I) -> Self
+    where
+        I: IntoIterator<Item = (usize, usize, u32)>,
+    {
+        let mut m = Self::new(len);
+        for (from, to, weight) in edges {
+            if weight < m.dist[from * len + to] {
+                m.set(from, to, weight);
+            }
+        }
+        m
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn get(&self, from: usize, to: usize) -> Option<u32> {
+        match self.dist[from * self.len + to] {
+            INF => None,
+            d => Some(d),
+        }
+    }
+
+    pub fn set(&mut self, from: usize, to: usize, dist: u32) {
+        self.dist[from * self.len + to] = dist;
+    }
+
+    pub fn set_undirected(&mut self, a: usize, b: usize, dist: u32) {
+        self.set(a, b, dist);
+        self.set(b, a, dist);
+    }
+
+    /// Relaxes the matrix in place into shortest distances between every pair of nodes.
+    pub fn floyd_warshall(&mut self) {
+        let n = self.len;
+        for k in 0..n {
+            for i in 0..n {
+                let ik = self.dist[i * n + k];
+                if ik == INF {
+                    continue;
+                }
+                for j in 0..n {
+                    let kj = self.dist[k * n + j];
+                    if kj == INF {
+                        continue;
+                    }
+                    let d = ik + kj;
+                    if d < self.dist[i * n + j] {
+                        self.dist[i * n + j] = d;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Matrix restricted to `keep`, where node `i` of the result is node `keep[i]` of this one.
+    /// Distances are kept as is, so relax with `floyd_warshall` first to route through the
+    /// nodes that are dropped.
+    pub fn contract(&self, keep: &[usize]) -> Self {
+        let len = keep.len();
+        let mut dist = Vec::with_capacity(len * len);
+        for &from in keep {
+            for &to in keep {
+                dist.push(self.dist[from * self.len + to]);
+            }
+        }
+        Self { len, dist }
+    }
+
+    /// Like `contract` for the nodes matching `pred`, also returning the kept original indices.
+    pub fn contract_filter<F>(&self, pred: F) -> (Self, Vec<usize>)
+    where
+        F: Fn(usize) -> bool,
+    {
+        let keep = (0..self.len).filter(|&i| pred(i)).collect::<Vec<_>>();
+        (self.contract(&keep), keep)
+    }
+}
+
+/// Set of node indices below 64 stored as a bitmask, cheap to copy and hash for search states.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct NodeSet(pub u64);
+
+impl NodeSet {
+    pub fn empty() -> Self {
+        Self(0)
+    }
+
+    /// Set of all nodes in `0..len`.
+    pub fn full(len: usize) -> Self {
+        assert!(len <= 64, "NodeSet holds at most 64 nodes");
+        if len == 64 {
+            Self(u64::MAX)
+        } else {
+            Self((1 << len) - 1)
+        }
+    }
+
+    pub fn contains(&self, node: usize) -> bool {
+        self.0 & (1 << node) != 0
+    }
+
+    pub fn with(self, node: usize) -> Self {
+        Self(self.0 | 1 << node)
+    }
+
+    pub fn without(self, node: usize) -> Self {
+        Self(self.0 & !(1 << node))
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    pub fn is_disjoint(&self, other: &NodeSet) -> bool {
+        self.0 & other.0 == 0
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = usize> {
+        let mut bits = self.0;
+        std::iter::from_fn(move || {
+            if bits == 0 {
+                None
+            } else {
+                let node = bits.trailing_zeros() as usize;
+                bits &= bits - 1;
+                Some(node)
+            }
+        })
+    }
+}
+
+impl FromIterator<usize> for NodeSet {
+    fn from_iter<T: IntoIterator<Item = usize>>(iter: T) -> Self {
+        iter.into_iter().fold(Self::empty(), |s, n| s.with(n))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Tunnels of the day16 example, AA=0 .. JJ=9, with the valves with a flow rate
+    const TUNNELS: &[(usize, usize)] = &[
+        (0, 3),
+        (0, 8),
+        (0, 1),
+        (1, 2),
+        (2, 3),
+        (3, 4),
+        (4, 5),
+        (5, 6),
+        (6, 7),
+        (8, 9),
+    ];
+    const VALVES: &[usize] = &[1, 2, 3, 4, 7, 9];
+
+    fn tunnels() -> DistanceMatrix {
+        let mut m = DistanceMatrix::new(10);
+        for &(a, b) in TUNNELS {
+            m.set_undirected(a, b, 1);
+        }
+        m
+    }
+
+    #[test]
+    fn test_floyd_warshall() {
+        let mut m = tunnels();
+        assert_eq!(m.get(0, 7), None);
+        m.floyd_warshall();
+        assert_eq!(m.get(0, 0), Some(0));
+        assert_eq!(m.get(0, 7), Some(5));
+        assert_eq!(m.get(9, 7), Some(7));
+        assert_eq!(m.get(2, 5), Some(3));
+        for i in 0..10 {
+            for j in 0..10 {
+                assert_eq!(m.get(i, j), m.get(j, i));
+            }
+        }
+    }
+
+    #[test]
+    fn test_directed_and_unreachable() {
+        let mut m = DistanceMatrix::from_edges(4, [(0, 1, 5), (1, 2, 1), (0, 2, 9), (0, 1, 3)]);
+        m.floyd_warshall();
+        assert_eq!(m.get(0, 2), Some(4));
+        assert_eq!(m.get(2, 0), None);
+        assert_eq!(m.get(0, 3), None);
+    }
+
+    #[test]
+    fn test_contract() {
+        let mut m = tunnels();
+        m.floyd_warshall();
+        let (c, nodes) = m.contract_filter(|i| i == 0 || VALVES.contains(&i));
+        assert_eq!(nodes, vec![0, 1, 2, 3, 4, 7, 9]);
+        assert_eq!(c.len(), 7);
+        for (i, &a) in nodes.iter().enumerate() {
+            for (j, &b) in nodes.iter().enumerate() {
+                assert_eq!(c.get(i, j), m.get(a, b));
+            }
+        }
+    }
+
+    #[test]
+    fn test_node_set() {
+        let s = [1, 3, 5].into_iter().collect::<NodeSet>();
+        assert_eq!(s.len(), 3);
+        assert!(s.contains(3) && !s.contains(2));
+        assert_eq!(s.iter().collect::<Vec<_>>(), vec![1, 3, 5]);
+        assert_eq!(
+            s.without(3).with(63).iter().collect::<Vec<_>>(),
+            vec![1, 5, 63]
+        );
+        assert!(s.is_disjoint(&NodeSet::empty().with(0)));
+        assert_eq!(NodeSet::full(64).len(), 64);
+        assert_eq!(NodeSet::full(3), NodeSet(0b111));
+    }
+}
@@ -0,0 +1,192 @@
+//! Searches where what's passable depends on time, repeating with a fixed period.
+
+use std::collections::{HashSet, VecDeque};
+use std::hash::Hash;
+
+/// Obstacle positions for every time step of one period.
+#[derive(Debug, Clone)]
+pub struct PeriodicObstacles<P> {
+    at: Vec<HashSet<P>>,
+}
+
+impl<P: Hash + Eq> PeriodicObstacles<P> {
+    /// Precomputes the obstacles for `t` in `0..period` using `obstacles_at`.
+    pub fn precompute<F, I>(period: usize, obstacles_at: F) -> Self
+    where
+        F: Fn(usize) -> I,
+        I: IntoIterator<Item = P>,
+    {
+        assert!(period > 0, "Period must be positive");
+        let at = (0..period)
+            .map(|t| obstacles_at(t).into_iter().collect())
+            .collect();
+        Self { at }
+    }
+
+    pub fn period(&self) -> usize {
+        self.at.len()
+    }
+
+    pub fn is_blocked(&self, pos: &P, time: usize) -> bool {
+        self.at[time % self.at.len()].contains(pos)
+    }
+}
+
+/// Breadth first search over `(position, time)` where every move, including waiting if `moves`
+/// yields the position itself, takes one time step. States are deduplicated on
+/// `(position, time % period)` which keeps the search finite. Returns the earliest time a goal
+/// position is reached, starting from `start` at `start_time`.
+pub fn earliest_arrival<P, M, I, G>(
+    start: P,
+    start_time: usize,
+    obstacles: &PeriodicObstacles<P>,
+    moves: M,
+    is_goal: G,
+) -> Option<usize>
+where
+    P: Hash + Eq + Clone,
+    M: Fn(&P) -> I,
+    I: IntoIterator<Item = P>,
+    G: Fn(&P) -> bool,
+{
+    let period = obstacles.period();
+    let mut visited = HashSet::new();
+    let mut queue = VecDeque::new();
+
+    visited.insert((start.clone(), start_time % period));
+    queue.push_back((start, start_time));
+
+    while let Some((pos, time)) = queue.pop_front() {
+        if is_goal(&pos) {
+            return Some(time);
+        }
+        let next_time = time + 1;
+        for next in moves(&pos) {
+            if obstacles.is_blocked(&next, next_time) {
+                continue;
+            }
+            if visited.insert((next.clone(), next_time % period)) {
+                queue.push_back((next, next_time));
+            }
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Complex example of day24
+    const VALLEY: &str = "#.######
+#>>.<^<#
+#.<..<<#
+#>v.><>#
+#<^v^^>#
+######.#";
+
+    struct Valley {
+        width: i32,
+        height: i32,
+        obstacles: PeriodicObstacles<(i32, i32)>,
+    }
+
+    impl Valley {
+        fn parse(s: &str) -> Self {
+            let rows = s.lines().collect::<Vec<_>>();
+            let width = rows[0].len() as i32 - 2;
+            let height = rows.len() as i32 - 2;
+
+            let mut blizzards = vec![];
+            for (y, row) in rows.iter().enumerate() {
+                for (x, c) in row.chars().enumerate() {
+                    let dir = match c {
+                        '>' => (1, 0),
+                        '<' => (-1, 0),
+                        'v' => (0, 1),
+                        '^' => (0, -1),
+                        _ => continue,
+                    };
+                    blizzards.push(((x as i32 - 1, y as i32 - 1), dir));
+                }
+            }
+
+            // Walls never move, only the interior wraps with period lcm(width, height)
+            let period = (width * height) as usize / gcd(width as usize, height as usize);
+            let obstacles = PeriodicObstacles::precompute(period, |t| {
+                let t = t as i32;
+                let moved = blizzards.iter().map(move |&((x, y), (dx, dy))| {
+                    (
+                        (x + dx * t).rem_euclid(width),
+                        (y + dy * t).rem_euclid(height),
+                    )
+                });
+                let walls = (-1..=width).flat_map(|x| [(x, -1), (x, height)]);
+                let walls = walls.chain((-1..=height).flat_map(|y| [(-1, y), (width, y)]));
+                let openings = [(0, -1), (width - 1, height)];
+                moved.chain(walls.filter(move |w| !openings.contains(w)))
+            });
+
+            Self {
+                width,
+                height,
+                obstacles,
+            }
+        }
+
+        fn moves(&self, &(x, y): &(i32, i32)) -> Vec<(i32, i32)> {
+            [(0, 0), (1, 0), (-1, 0), (0, 1), (0, -1)]
+                .into_iter()
+                .map(|(dx, dy)| (x + dx, y + dy))
+                .filter(|&(x, y)| y >= -1 && y <= self.height)
+                .collect()
+        }
+
+        fn cross(&self, from: (i32, i32), to: (i32, i32), time: usize) -> Option<usize> {
+            earliest_arrival(from, time, &self.obstacles, |p| self.moves(p), |p| *p == to)
+        }
+    }
+
+    fn gcd(a: usize, b: usize) -> usize {
+        if b == 0 {
+            a
+        } else {
+            gcd(b, a % b)
+        }
+    }
+
+    #[test]
+    fn test_periodic_obstacles() {
+        let obstacles = PeriodicObstacles::precompute(3, |t| [t * 10]);
+        assert_eq!(obstacles.period(), 3);
+        assert!(obstacles.is_blocked(&10, 1));
+        assert!(obstacles.is_blocked(&10, 4));
+        assert!(!obstacles.is_blocked(&10, 2));
+    }
+
+    #[test]
+    fn test_day24_example() {
+        let valley = Valley::parse(VALLEY);
+        assert_eq!(valley.obstacles.period(), 12);
+
+        let start = (0, -1);
+        let goal = (valley.width - 1, valley.height);
+        let there = valley.cross(start, goal, 0).unwrap();
+        assert_eq!(there, 18);
+        let back = valley.cross(goal, start, there).unwrap();
+        assert_eq!(back, 18 + 23);
+        assert_eq!(valley.cross(start, goal, back), Some(54));
+    }
+
+    #[test]
+    fn test_unreachable() {
+        // A wall between start and goal that never opens
+        let obstacles = PeriodicObstacles::precompute(2, |_| [5]);
+        let moves = |p: &i32| [*p - 1, *p, *p + 1];
+        let arrival = earliest_arrival(0, 0, &obstacles, moves, |p| *p == 10);
+        assert_eq!(arrival, None);
+        let arrival = earliest_arrival(0, 0, &obstacles, moves, |p| *p == 4);
+        assert_eq!(arrival, Some(4));
+    }
+}
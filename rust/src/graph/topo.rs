@@ -0,0 +1,266 @@
+//! Evaluation of values defined by a directed acyclic graph of dependencies.
+
+use std::collections::VecDeque;
+use std::error::Error;
+use std::fmt::Display;
+
+/// Error for dependency graphs that aren't acyclic, holds the nodes of one cycle in order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cycle(pub Vec<usize>);
+
+impl Display for Cycle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Dependency cycle through nodes {:?}", self.0)
+    }
+}
+
+impl Error for Cycle {}
+
+/// Evaluated graph which keeps enough structure to re-evaluate after a node changes.
+pub struct Evaluation<N, V, F> {
+    nodes: Vec<N>,
+    dependents: Vec<Vec<usize>>,
+    deps: Vec<Vec<usize>>,
+    order: Vec<usize>,
+    order_pos: Vec<usize>,
+    values: Vec<V>,
+    eval: F,
+}
+
+/// Evaluates every node after the nodes it depends on. `deps[i]` lists the nodes `nodes[i]`
+/// depends on and `eval` gets the node with the values of those dependencies in the same order.
+pub fn topo_eval<N, V, F>(
+    nodes: Vec<N>,
+    deps: Vec<Vec<usize>>,
+    eval: F,
+) -> Result<Evaluation<N, V, F>, Cycle>
+where
+    F: Fn(&N, &[&V]) -> V,
+{
+    assert_eq!(
+        nodes.len(),
+        deps.len(),
+        "Every node needs a dependency list"
+    );
+    let n = nodes.len();
+
+    let mut dependents = vec![vec![]; n];
+    let mut missing = vec![0; n];
+    for (node, ds) in deps.iter().enumerate() {
+        missing[node] = ds.len();
+        for &d in ds {
+            dependents[d].push(node);
+        }
+    }
+
+    // Kahn's algorithm
+    let mut queue = (0..n).filter(|&i| missing[i] == 0).collect::<VecDeque<_>>();
+    let mut order = Vec::with_capacity(n);
+    while let Some(node) = queue.pop_front() {
+        order.push(node);
+        for &d in &dependents[node] {
+            missing[d] -= 1;
+            if missing[d] == 0 {
+                queue.push_back(d);
+            }
+        }
+    }
+    if order.len() < n {
+        return Err(find_cycle(&deps, &missing));
+    }
+
+    let mut order_pos = vec![0; n];
+    for (pos, &node) in order.iter().enumerate() {
+        order_pos[node] = pos;
+    }
+
+    let mut values = Vec::<Option<V>>::with_capacity(n);
+    values.resize_with(n, || None);
+    for &node in &order {
+        let args = deps[node]
+            .iter()
+            .map(|&d| values[d].as_ref().unwrap())
+            .collect::<Vec<_>>();
+        values[node] = Some(eval(&nodes[node], &args));
+    }
+
+    Ok(Evaluation {
+        nodes,
+        dependents,
+        deps,
+        order,
+        order_pos,
+        values: values.into_iter().map(Option::unwrap).collect(),
+        eval,
+    })
+}
+
+// Walks unresolved dependencies from an unresolved node until a node repeats
+fn find_cycle(deps: &[Vec<usize>], missing: &[usize]) -> Cycle {
+    let mut node = (0..deps.len()).find(|&i| missing[i] > 0).unwrap();
+    let mut path = vec![];
+    let mut on_path = vec![None; deps.len()];
+    loop {
+        if let Some(start) = on_path[node] {
+            return Cycle(path[start..].to_vec());
+        }
+        on_path[node] = Some(path.len());
+        path.push(node);
+        node = *deps[node].iter().find(|&&d| missing[d] > 0).unwrap();
+    }
+}
+
+impl<N, V, F> Evaluation<N, V, F>
+where
+    V: PartialEq,
+    F: Fn(&N, &[&V]) -> V,
+{
+    pub fn value(&self, node: usize) -> &V {
+        &self.values[node]
+    }
+
+    pub fn values(&self) -> &[V] {
+        &self.values
+    }
+
+    pub fn node(&self, node: usize) -> &N {
+        &self.nodes[node]
+    }
+
+    /// Nodes in the order they were evaluated.
+    pub fn order(&self) -> &[usize] {
+        &self.order
+    }
+
+    /// Replaces the definition of `node`, keeping its dependencies, and re-evaluates only the
+    /// nodes downstream of it whose inputs actually changed. Returns the number of evaluations.
+    pub fn set(&mut self, node: usize, definition: N) -> usize {
+        self.nodes[node] = definition;
+
+        let mut dirty = vec![false; self.nodes.len()];
+        dirty[node] = true;
+        let mut evaluations = 0;
+
+        for pos in self.order_pos[node]..self.order.len() {
+            let n = self.order[pos];
+            if !dirty[n] {
+                continue;
+            }
+            let args = self.deps[n]
+                .iter()
+                .map(|&d| &self.values[d])
+                .collect::<Vec<_>>();
+            let value = (self.eval)(&self.nodes[n], &args);
+            evaluations += 1;
+
+            if value != self.values[n] {
+                self.values[n] = value;
+                for &d in &self.dependents[n] {
+                    dirty[d] = true;
+                }
+            }
+        }
+
+        evaluations
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+
+    const MONKEYS: &str = "root: pppw + sjmn
+dbpl: 5
+cczh: sllz + lgvd
+zczc: 2
+ptdq: humn - dvpt
+dvpt: 3
+lfqf: 4
+humn: 5
+ljgn: 2
+sjmn: drzm * dbpl
+sllz: 4
+pppw: cczh / lfqf
+lgvd: ljgn * ptdq
+drzm: hmdt - zczc
+hmdt: 32";
+
+    #[derive(Debug)]
+    enum Job {
+        Number(i64),
+        Op(char),
+    }
+
+    fn monkeys() -> (Vec<&'static str>, Vec<Job>, Vec<Vec<usize>>) {
+        let lines = MONKEYS
+            .lines()
+            .map(|l| l.split_once(": ").unwrap())
+            .collect::<Vec<_>>();
+        let idx = lines
+            .iter()
+            .enumerate()
+            .map(|(i, (name, _))| (*name, i))
+            .collect::<HashMap<_, _>>();
+
+        let mut jobs = vec![];
+        let mut deps = vec![];
+        for (_, job) in &lines {
+            match job.split(' ').collect::<Vec<_>>()[..] {
+                [a, op, b] => {
+                    jobs.push(Job::Op(op.chars().next().unwrap()));
+                    deps.push(vec![idx[a], idx[b]]);
+                }
+                [n] => {
+                    jobs.push(Job::Number(n.parse().unwrap()));
+                    deps.push(vec![]);
+                }
+                _ => unreachable!(),
+            }
+        }
+        (lines.iter().map(|(n, _)| *n).collect(), jobs, deps)
+    }
+
+    fn eval(job: &Job, args: &[&i64]) -> i64 {
+        match (job, args) {
+            (Job::Number(n), []) => *n,
+            (Job::Op('+'), [a, b]) => *a + *b,
+            (Job::Op('-'), [a, b]) => *a - *b,
+            (Job::Op('*'), [a, b]) => *a * *b,
+            (Job::Op('/'), [a, b]) => *a / *b,
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn test_day21_example() -> Result<(), Cycle> {
+        let (names, jobs, deps) = monkeys();
+        let root = names.iter().position(|&n| n == "root").unwrap();
+        let humn = names.iter().position(|&n| n == "humn").unwrap();
+
+        let mut evaluation = topo_eval(jobs, deps, eval)?;
+        assert_eq!(*evaluation.value(root), 152);
+        assert_eq!(evaluation.order().last(), Some(&root));
+
+        // Only humn and the nodes between it and root are affected
+        let evaluations = evaluation.set(humn, Job::Number(301));
+        assert_eq!(evaluations, 6);
+        assert_eq!(*evaluation.value(root), 150 + 150);
+
+        // Unchanged values don't propagate further
+        assert_eq!(evaluation.set(humn, Job::Number(301)), 1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_cycle() {
+        let deps = vec![vec![1], vec![2], vec![3], vec![1], vec![]];
+        let result = topo_eval(vec![(); 5], deps, |_, _: &[&i32]| 0);
+        let Err(Cycle(mut cycle)) = result else {
+            panic!("Expected a cycle");
+        };
+        cycle.sort();
+        assert_eq!(cycle, vec![1, 2, 3]);
+    }
+}
@@ -2,6 +2,14 @@ extern crate time;
 
 use std::time::*;
 
+pub mod automaton;
+pub mod cubenet;
+pub mod generate;
+pub mod graph;
+pub mod meta;
+pub mod ring;
+pub mod search;
+
 pub fn measure<F, S, T>(f: F) -> Result<S, T>
 where
     F: Fn() -> Result<S, T>,
@@ -0,0 +1,119 @@
+//! Static metadata about each day's solution.
+
+use std::fmt::Display;
+
+/// Expected growth of a solution's runtime in the size of its input.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Complexity {
+    Linear,
+    Linearithmic,
+    Quadratic,
+    Polynomial(f64),
+}
+
+impl Complexity {
+    /// Exponent of the dominating polynomial term, the log factor is counted as a small addition.
+    pub fn exponent(&self) -> f64 {
+        match self {
+            Self::Linear => 1.0,
+            Self::Linearithmic => 1.15,
+            Self::Quadratic => 2.0,
+            Self::Polynomial(e) => *e,
+        }
+    }
+}
+
+impl Display for Complexity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Linear => write!(f, "O(n)"),
+            Self::Linearithmic => write!(f, "O(n log n)"),
+            Self::Quadratic => write!(f, "O(n²)"),
+            Self::Polynomial(e) => write!(f, "O(n^{})", e),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct DayMeta {
+    pub day: u8,
+    pub title: &'static str,
+    pub complexity: Complexity,
+}
+
+pub const DAYS: &[DayMeta] = &[
+    DayMeta {
+        day: 1,
+        title: "Calorie Counting",
+        complexity: Complexity::Linearithmic,
+    },
+    DayMeta {
+        day: 2,
+        title: "Rock Paper Scissors",
+        complexity: Complexity::Linear,
+    },
+    DayMeta {
+        day: 3,
+        title: "Rucksack Reorganization",
+        complexity: Complexity::Linear,
+    },
+    DayMeta {
+        day: 4,
+        title: "Camp Cleanup",
+        complexity: Complexity::Linear,
+    },
+    DayMeta {
+        day: 5,
+        title: "Supply Stacks",
+        complexity: Complexity::Linear,
+    },
+    DayMeta {
+        day: 6,
+        title: "Tuning Trouble",
+        complexity: Complexity::Linear,
+    },
+    DayMeta {
+        day: 7,
+        title: "No Space Left On Device",
+        complexity: Complexity::Linear,
+    },
+    DayMeta {
+        day: 8,
+        title: "Treetop Tree House",
+        complexity: Complexity::Polynomial(1.5),
+    },
+    DayMeta {
+        day: 9,
+        title: "Rope Bridge",
+        complexity: Complexity::Linear,
+    },
+    DayMeta {
+        day: 10,
+        title: "Cathode-Ray Tube",
+        complexity: Complexity::Linear,
+    },
+    DayMeta {
+        day: 11,
+        title: "Monkey in the Middle",
+        complexity: Complexity::Linear,
+    },
+    DayMeta {
+        day: 12,
+        title: "Hill Climbing Algorithm",
+        complexity: Complexity::Quadratic,
+    },
+    DayMeta {
+        day: 13,
+        title: "Distress Signal",
+        complexity: Complexity::Linearithmic,
+    },
+    DayMeta {
+        day: 14,
+        title: "Regolith Reservoir",
+        complexity: Complexity::Quadratic,
+    },
+];
+
+pub fn day(day: u8) -> Option<&'static DayMeta> {
+    DAYS.iter().find(|d| d.day == day)
+}
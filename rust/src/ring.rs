@@ -0,0 +1,213 @@
+//! Circular list with stable element identities and fast positional moves.
+
+const REMOVED: usize = usize::MAX;
+
+/// Circular sequence of values where every value keeps the id it was created with, its index in
+/// the initial vector, no matter how it's moved around.
+///
+/// Ids are stored in buckets of roughly √n elements, so finding the position of an id,
+/// removing it and inserting it at a position are all O(√n). Buckets that grow too large
+/// trigger a rebuild which amortises to the same cost.
+#[derive(Debug, Clone)]
+pub struct IndexedRing<T> {
+    values: Vec<T>,
+    buckets: Vec<Vec<usize>>,
+    bucket_of: Vec<usize>,
+    bucket_size: usize,
+    len: usize,
+}
+
+impl<T> IndexedRing<T> {
+    pub fn new(values: Vec<T>) -> Self {
+        let len = values.len();
+        let mut ring = Self {
+            values,
+            buckets: vec![],
+            bucket_of: vec![REMOVED; len],
+            bucket_size: 1,
+            len,
+        };
+        ring.rebuild((0..len).collect());
+        ring
+    }
+
+    fn rebuild(&mut self, ids: Vec<usize>) {
+        self.bucket_size = ((ids.len() as f64).sqrt() as usize).max(16);
+        self.buckets = ids.chunks(self.bucket_size).map(|c| c.to_vec()).collect();
+        if self.buckets.is_empty() {
+            self.buckets.push(vec![]);
+        }
+        for (b, bucket) in self.buckets.iter().enumerate() {
+            for &id in bucket {
+                self.bucket_of[id] = b;
+            }
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn value(&self, id: usize) -> &T {
+        &self.values[id]
+    }
+
+    pub fn contains(&self, id: usize) -> bool {
+        self.bucket_of.get(id).is_some_and(|&b| b != REMOVED)
+    }
+
+    /// Current position of `id`, which must be in the ring.
+    pub fn position(&self, id: usize) -> usize {
+        let b = self.bucket_of[id];
+        assert!(b != REMOVED, "Id {} isn't in the ring", id);
+        let before = self.buckets[..b].iter().map(Vec::len).sum::<usize>();
+        before + self.buckets[b].iter().position(|&i| i == id).unwrap()
+    }
+
+    /// Id at position `pos` counted circularly from the start.
+    pub fn id_at(&self, pos: usize) -> usize {
+        assert!(!self.is_empty(), "Empty ring");
+        let mut pos = pos % self.len;
+        for bucket in &self.buckets {
+            if pos < bucket.len() {
+                return bucket[pos];
+            }
+            pos -= bucket.len();
+        }
+        unreachable!()
+    }
+
+    /// Removes `id` from the ring returning the position it had.
+    pub fn remove(&mut self, id: usize) -> usize {
+        let pos = self.position(id);
+        let b = self.bucket_of[id];
+        self.buckets[b].retain(|&i| i != id);
+        self.bucket_of[id] = REMOVED;
+        self.len -= 1;
+        pos
+    }
+
+    /// Inserts a removed `id` so that it ends up at position `pos`, `pos` may equal the length.
+    pub fn insert(&mut self, pos: usize, id: usize) {
+        assert!(!self.contains(id), "Id {} is already in the ring", id);
+        assert!(pos <= self.len, "Position {} out of bounds", pos);
+
+        let mut rest = pos;
+        let mut b = 0;
+        while b < self.buckets.len() - 1 && rest > self.buckets[b].len() {
+            rest -= self.buckets[b].len();
+            b += 1;
+        }
+        self.buckets[b].insert(rest, id);
+        self.bucket_of[id] = b;
+        self.len += 1;
+
+        if self.buckets[b].len() > self.bucket_size * 2 {
+            let ids = self.buckets.concat();
+            self.rebuild(ids);
+        }
+    }
+
+    /// Moves `id` `offset` steps forward (or backward if negative) around the ring, the way
+    /// day20 mixes numbers: the element is lifted out so a full lap is `len - 1` steps.
+    pub fn move_by(&mut self, id: usize, offset: i64) {
+        if self.len < 2 {
+            return;
+        }
+        let pos = self.remove(id) as i64;
+        let new_pos = (pos + offset).rem_euclid(self.len as i64) as usize;
+        self.insert(new_pos, id);
+    }
+
+    /// Ids in ring order starting at position 0.
+    pub fn ids(&self) -> impl Iterator<Item = usize> + '_ {
+        self.buckets.iter().flatten().copied()
+    }
+
+    /// Values in ring order starting at position 0.
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.ids().map(|id| &self.values[id])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generate::Rng;
+
+    // Naive reference implementation
+    fn naive_move(ids: &mut Vec<usize>, id: usize, offset: i64) {
+        let pos = ids.iter().position(|&i| i == id).unwrap() as i64;
+        ids.remove(pos as usize);
+        let new_pos = (pos + offset).rem_euclid(ids.len() as i64) as usize;
+        ids.insert(new_pos, id);
+    }
+
+    #[test]
+    fn test_day20_example() {
+        let values = vec![1, 2, -3, 3, -2, 0, 4];
+        let mut ring = IndexedRing::new(values.clone());
+        for (id, v) in values.iter().enumerate() {
+            ring.move_by(id, *v);
+        }
+        let zero = ring.position(5);
+        let grove = [1000, 2000, 3000].map(|n| *ring.value(ring.id_at(zero + n)));
+        assert_eq!(grove, [4, -3, 2]);
+    }
+
+    #[test]
+    fn test_against_naive_moves() {
+        let mut rng = Rng::new(20);
+        for len in [1, 2, 3, 17, 200, 1000] {
+            let mut ring = IndexedRing::new((0..len).collect::<Vec<_>>());
+            let mut naive = (0..len).collect::<Vec<_>>();
+            for _ in 0..2000 {
+                let id = rng.below(len as u64) as usize;
+                let offset = rng.between(0, 20000) as i64 - 10000;
+                ring.move_by(id, offset);
+                if len > 1 {
+                    naive_move(&mut naive, id, offset);
+                }
+                assert_eq!(
+                    ring.position(id),
+                    naive.iter().position(|&i| i == id).unwrap()
+                );
+            }
+            assert_eq!(ring.ids().collect::<Vec<_>>(), naive);
+            assert_eq!(ring.iter().copied().collect::<Vec<_>>(), naive);
+        }
+    }
+
+    #[test]
+    fn test_against_naive_remove_insert() {
+        let mut rng = Rng::new(7);
+        let len = 500;
+        let mut ring = IndexedRing::new(vec![(); len]);
+        let mut naive = (0..len).collect::<Vec<_>>();
+        let mut removed = vec![];
+
+        for _ in 0..5000 {
+            if !removed.is_empty() && (naive.is_empty() || rng.below(2) == 0) {
+                let id = removed.swap_remove(rng.below(removed.len() as u64) as usize);
+                let pos = rng.below(naive.len() as u64 + 1) as usize;
+                ring.insert(pos, id);
+                naive.insert(pos, id);
+            } else {
+                let id = *rng.pick(&naive);
+                let pos = ring.remove(id);
+                assert_eq!(naive.remove(pos), id);
+                removed.push(id);
+                assert!(!ring.contains(id));
+            }
+            assert_eq!(ring.len(), naive.len());
+        }
+        assert_eq!(ring.ids().collect::<Vec<_>>(), naive);
+        for (pos, &id) in naive.iter().enumerate() {
+            assert_eq!(ring.id_at(pos), id);
+        }
+    }
+}
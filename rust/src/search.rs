@@ -0,0 +1,170 @@
+//! Generic search strategies for optimisation puzzles.
+
+/// Counters describing how much work a search did.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Stats {
+    /// States whose successors were generated.
+    pub expanded: usize,
+    /// States discarded because their upper bound couldn't beat the best known value.
+    pub pruned: usize,
+    /// States rejected by the visitation hook.
+    pub duplicates: usize,
+}
+
+#[derive(Debug, Clone)]
+pub struct Outcome<S, V> {
+    /// Best value found, the lower bound of `best_state`.
+    pub best: V,
+    pub best_state: S,
+    pub stats: Stats,
+}
+
+/// Depth first branch and bound maximising over the states reachable from `start`.
+///
+/// - `expand` pushes the successors of a state, states without successors are leaves.
+/// - `lower` is a value known to be achievable from a state, e.g. the score when doing nothing
+///   more. The best lower bound seen is the result.
+/// - `upper` is an optimistic bound of any value reachable from a state, states whose upper
+///   bound doesn't exceed the best value so far are pruned. It must never underestimate.
+/// - `visit` returns false for states that have already been seen and should be skipped.
+pub fn branch_and_bound<S, V, E, L, U, D>(
+    start: S,
+    mut expand: E,
+    lower: L,
+    upper: U,
+    mut visit: D,
+) -> Outcome<S, V>
+where
+    S: Clone,
+    V: Ord + Copy,
+    E: FnMut(&S, &mut Vec<S>),
+    L: Fn(&S) -> V,
+    U: Fn(&S) -> V,
+    D: FnMut(&S) -> bool,
+{
+    let mut stats = Stats::default();
+    let mut best = lower(&start);
+    let mut best_state = start.clone();
+    let mut stack = vec![start];
+    let mut successors = vec![];
+
+    while let Some(state) = stack.pop() {
+        if !visit(&state) {
+            stats.duplicates += 1;
+            continue;
+        }
+
+        let value = lower(&state);
+        if value > best {
+            best = value;
+            best_state = state.clone();
+        }
+
+        if upper(&state) <= best {
+            stats.pruned += 1;
+            continue;
+        }
+
+        stats.expanded += 1;
+        successors.clear();
+        expand(&state, &mut successors);
+        stack.append(&mut successors);
+    }
+
+    Outcome {
+        best,
+        best_state,
+        stats,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+
+    // (weight, value)
+    const ITEMS: &[(u32, u32)] = &[(12, 4), (2, 2), (1, 1), (1, 1), (4, 10), (3, 7), (7, 9)];
+    const CAPACITY: u32 = 15;
+
+    #[derive(Debug, Clone, Hash, PartialEq, Eq)]
+    struct Knapsack {
+        next: usize,
+        weight: u32,
+        value: u32,
+    }
+
+    fn brute_force() -> u32 {
+        (0..1u32 << ITEMS.len())
+            .filter_map(|mask| {
+                let (w, v) = ITEMS
+                    .iter()
+                    .enumerate()
+                    .filter(|(i, _)| mask & (1 << i) != 0)
+                    .fold((0, 0), |(w, v), (_, (iw, iv))| (w + iw, v + iv));
+                (w <= CAPACITY).then_some(v)
+            })
+            .max()
+            .unwrap()
+    }
+
+    fn expand(s: &Knapsack, out: &mut Vec<Knapsack>) {
+        if let Some(&(w, v)) = ITEMS.get(s.next) {
+            out.push(Knapsack {
+                next: s.next + 1,
+                ..s.clone()
+            });
+            if s.weight + w <= CAPACITY {
+                out.push(Knapsack {
+                    next: s.next + 1,
+                    weight: s.weight + w,
+                    value: s.value + v,
+                });
+            }
+        }
+    }
+
+    fn start() -> Knapsack {
+        Knapsack {
+            next: 0,
+            weight: 0,
+            value: 0,
+        }
+    }
+
+    fn optimistic(s: &Knapsack) -> u32 {
+        s.value + ITEMS[s.next..].iter().map(|(_, v)| v).sum::<u32>()
+    }
+
+    #[test]
+    fn test_knapsack() {
+        let no_pruning = branch_and_bound(start(), expand, |s| s.value, |_| u32::MAX, |_| true);
+        let pruning = branch_and_bound(start(), expand, |s| s.value, optimistic, |_| true);
+
+        assert_eq!(no_pruning.best, brute_force());
+        assert_eq!(pruning.best, brute_force());
+        assert_eq!(pruning.best_state.value, pruning.best);
+        assert_eq!(no_pruning.stats.pruned, 0);
+        assert!(pruning.stats.pruned > 0);
+        assert!(pruning.stats.expanded < no_pruning.stats.expanded);
+    }
+
+    #[test]
+    fn test_dedup() {
+        // Taking either of the two identical items leads to the same state
+        let mut seen = HashSet::new();
+        let dedup = branch_and_bound(
+            start(),
+            expand,
+            |s| s.value,
+            |_| u32::MAX,
+            |s| seen.insert(s.clone()),
+        );
+        let all = branch_and_bound(start(), expand, |s| s.value, |_| u32::MAX, |_| true);
+
+        assert_eq!(dedup.best, brute_force());
+        assert!(dedup.stats.duplicates > 0);
+        assert!(dedup.stats.expanded < all.stats.expanded);
+    }
+}